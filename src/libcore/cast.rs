@@ -60,6 +60,31 @@ pub unsafe fn transmute_copy<T, U>(src: &T) -> U {
     dest
 }
 
+/// View any value as the `size_of::<T>()` bytes that make it up.
+#[inline(always)]
+pub unsafe fn as_byte_slice<'a, T>(val: &'a T) -> &'a [u8] {
+    let data: *u8 = rusti::transmute(val);
+    transmute(unstable::raw::Slice { data: data, len: sys::size_of::<T>() })
+}
+
+/**
+ * Copy `bytes` into a freshly initialized `T`. Fails unless `bytes.len()`
+ * is exactly `size_of::<T>()`.
+ */
+#[inline(always)]
+pub unsafe fn from_byte_slice<T>(bytes: &[u8]) -> T {
+    assert!(bytes.len() == sys::size_of::<T>());
+    let mut dest: T = unstable::intrinsics::init();
+    {
+        let dest_ptr: *mut u8 = rusti::transmute(&mut dest);
+        let src_ptr: *u8 = rusti::transmute(bytes.as_ptr());
+        unstable::intrinsics::memmove64(dest_ptr,
+                                        src_ptr,
+                                        sys::size_of::<T>() as u64);
+    }
+    dest
+}
+
 /**
  * Move a thing into the void
  *
@@ -103,6 +128,32 @@ pub unsafe fn transmute<L, G>(thing: L) -> G {
     rusti::transmute(thing)
 }
 
+/// The reason a `try_transmute` call was refused. Carries the original
+/// value back, since the transmute did not happen and the caller would
+/// otherwise have no way to reclaim or drop it.
+pub enum TransmuteError<L> {
+    /// `L` and `G` do not have the same size.
+    SizeMismatch { thing: L, from: uint, to: uint },
+}
+
+/**
+ * Like `transmute`, but checks that `L` and `G` have the same size before
+ * reinterpreting `thing`, returning a `TransmuteError` instead of silently
+ * corrupting memory on mismatch. (Alignment is not a by-value concern:
+ * unlike `transmute_copy`, which reads through a pointer, `transmute`
+ * moves the bits of an owned value directly into a freshly typed slot, so
+ * there is no source address whose alignment could matter.)
+ */
+#[inline(always)]
+pub unsafe fn try_transmute<L, G>(thing: L) -> Result<G, TransmuteError<L>> {
+    let from = sys::size_of::<L>();
+    let to = sys::size_of::<G>();
+    if from != to {
+        return Err(SizeMismatch { thing: thing, from: from, to: to });
+    }
+    Ok(transmute(thing))
+}
+
 /// Coerce an immutable reference to be mutable.
 #[inline(always)]
 pub unsafe fn transmute_mut<'a,T>(ptr: &'a T) -> &'a mut T { transmute(ptr) }
@@ -149,6 +200,38 @@ pub unsafe fn copy_lifetime_vec<'a,S,T>(_ptr: &'a [S], ptr: &T) -> &'a T {
     transmute_region(ptr)
 }
 
+/// Build the `Slice<U>` repr for a `[T]` of `len` elements starting at
+/// `data`, recomputing the element count so the byte length is preserved.
+#[inline(always)]
+unsafe fn reinterpret_slice_repr<T, U>(data: *T, len: uint) -> unstable::raw::Slice<U> {
+    let bytes = len * sys::size_of::<T>();
+    let data: *U = transmute(data);
+    // A zero-sized `U` has no byte stride to divide `bytes` by; there's one
+    // `U` "at" every `T`, so the element count just passes through.
+    let new_len = if sys::size_of::<U>() == 0 {
+        len
+    } else {
+        debug_assert!(bytes % sys::size_of::<U>() == 0);
+        bytes / sys::size_of::<U>()
+    };
+    debug_assert!(data as uint % sys::align_of::<U>() == 0);
+    unstable::raw::Slice { data: data, len: new_len }
+}
+
+/// Reinterpret a slice of `T` as a slice of `U`, recomputing the element
+/// count so the total byte length is preserved. Useful for viewing e.g.
+/// `&[u32]` as `&[u8]`, or `&[u8]` as `&[f32]`.
+#[inline(always)]
+pub unsafe fn transmute_slice<'a,T,U>(src: &'a [T]) -> &'a [U] {
+    transmute(reinterpret_slice_repr::<T, U>(src.as_ptr(), src.len()))
+}
+
+/// Mutable counterpart to `transmute_slice`.
+#[inline(always)]
+pub unsafe fn transmute_slice_mut<'a,T,U>(src: &'a mut [T]) -> &'a mut [U] {
+    transmute(reinterpret_slice_repr::<T, U>(src.as_mut_ptr() as *T, src.len()))
+}
+
 
 /****************************************************************************
  * Tests
@@ -204,4 +287,90 @@ mod tests {
             assert!(~[76u8, 0u8] == transmute(~"L"));
         }
     }
+
+    #[test]
+    fn test_transmute_slice_widen() {
+        unsafe {
+            let bytes: [u8, ..4] = [0x78, 0x56, 0x34, 0x12];
+            let words: &[u32] = ::cast::transmute_slice(bytes.as_slice());
+            assert!(words.len() == 1);
+            assert!(words[0] == 0x12345678);
+        }
+    }
+
+    #[test]
+    fn test_transmute_slice_narrow() {
+        unsafe {
+            let words: [u32, ..2] = [0x12345678, 0xdeadbeef];
+            let bytes: &[u8] = ::cast::transmute_slice(words.as_slice());
+            assert!(bytes.len() == 8);
+            assert!(bytes[0] == 0x78 && bytes[3] == 0x12);
+        }
+    }
+
+    #[test]
+    fn test_transmute_slice_mut() {
+        unsafe {
+            let mut words: [u32, ..1] = [0];
+            {
+                let bytes: &mut [u8] = ::cast::transmute_slice_mut(words.as_mut_slice());
+                bytes[0] = 0xef;
+                bytes[1] = 0xbe;
+                bytes[2] = 0xad;
+                bytes[3] = 0xde;
+            }
+            assert!(words[0] == 0xdeadbeef);
+        }
+    }
+
+    #[test]
+    fn test_try_transmute_ok() {
+        unsafe {
+            match ::cast::try_transmute::<u32, i32>(0xdeadbeef) {
+                Ok(n) => assert!(n == 0xdeadbeef_u32 as i32),
+                Err(_) => fail!("same-size try_transmute should not fail"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_transmute_size_mismatch() {
+        unsafe {
+            match ::cast::try_transmute::<u8, u32>(1u8) {
+                Ok(_) => fail!("mismatched-size try_transmute should not succeed"),
+                Err(::cast::SizeMismatch { thing, from, to }) => {
+                    assert!(thing == 1u8);
+                    assert!(from == 1);
+                    assert!(to == 4);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_as_byte_slice() {
+        unsafe {
+            let n: u32 = 0x12345678;
+            let bytes = ::cast::as_byte_slice(&n);
+            assert!(bytes == [0x78, 0x56, 0x34, 0x12]);
+        }
+    }
+
+    #[test]
+    fn test_from_byte_slice() {
+        unsafe {
+            let bytes: [u8, ..4] = [0x78, 0x56, 0x34, 0x12];
+            let n: u32 = ::cast::from_byte_slice(bytes.as_slice());
+            assert!(n == 0x12345678);
+        }
+    }
+
+    #[test]
+    fn test_byte_slice_round_trip() {
+        unsafe {
+            let n: u32 = 0xcafef00d;
+            let back: u32 = ::cast::from_byte_slice(::cast::as_byte_slice(&n));
+            assert!(back == n);
+        }
+    }
 }